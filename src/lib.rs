@@ -1,123 +1,512 @@
 use concordium_std::*;
 
-#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
-enum PiggyBankState {
+#[derive(Serialize, SchemaType, PartialEq, Eq, Debug, Clone, Copy)]
+enum PiggyBankStatus {
     Intact,
     Smashed,
 }
 
+// Not `#[derive(SchemaType)]`: `StateMap` has no `SchemaType` impl, so this
+// can no longer derive it now that `contributions` lives in state. The
+// schema consumers actually need is exported through `PiggyBankView` (for
+// `view`) and `PiggyBankEvent` (for logged events) instead.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct PiggyBankState<S: HasStateApi = StateApi> {
+    owner: AccountAddress,
+    contributions: StateMap<AccountAddress, Amount, S>,
+    status: PiggyBankStatus,
+    goal: Option<Amount>,
+    beneficiary: Option<Address>,
+    fee_basis_points: u16,
+}
+
+impl<S: HasStateApi> PiggyBankState<S> {
+    fn new(
+        owner: AccountAddress,
+        goal: Option<Amount>,
+        beneficiary: Option<Address>,
+        fee_basis_points: u16,
+        state_builder: &mut StateBuilder<S>,
+    ) -> Self {
+        PiggyBankState {
+            owner,
+            contributions: state_builder.new_map(),
+            status: PiggyBankStatus::Intact,
+            goal,
+            beneficiary,
+            fee_basis_points,
+        }
+    }
+
+    fn total_contributions(&self) -> Amount {
+        self.contributions
+            .iter()
+            .fold(Amount::zero(), |acc, (_, amount)| acc + *amount)
+    }
+}
+
+#[derive(Serialize, SchemaType)]
+struct PiggyBankView {
+    owner: AccountAddress,
+    status: PiggyBankStatus,
+    balance: Amount,
+    goal: Option<Amount>,
+    contributions: Vec<(AccountAddress, Amount)>,
+}
+
+#[derive(Serialize, SchemaType)]
+struct InitParams {
+    goal: Option<Amount>,
+    beneficiary: Option<Address>,
+    fee_basis_points: u16,
+}
+
+#[derive(Debug, PartialEq, Eq, Serial, Reject)]
+enum InsertError {
+    AlreadySmashed,
+    ZeroAmount,
+    LogFull,
+    LogMalformed,
+}
+
+impl From<LogError> for InsertError {
+    fn from(le: LogError) -> Self {
+        match le {
+            LogError::Full => Self::LogFull,
+            LogError::Malformed => Self::LogMalformed,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Serial, Reject)]
 enum SmashError {
     NotOwner,
     AlreadySmashed,
     TransferError,
+    AmountNotZero,
+    Overflow,
+    FeeTransferError,
+    LogFull,
+    LogMalformed,
+}
+
+impl From<LogError> for SmashError {
+    fn from(le: LogError) -> Self {
+        match le {
+            LogError::Full => Self::LogFull,
+            LogError::Malformed => Self::LogMalformed,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serial, Reject)]
+enum WithdrawError {
+    #[from(ParseError)]
+    ParseParams,
+    NotOwner,
+    AlreadySmashed,
+    InsufficientFunds,
+    TransferError,
+    LogFull,
+    LogMalformed,
+}
+
+impl From<LogError> for WithdrawError {
+    fn from(le: LogError) -> Self {
+        match le {
+            LogError::Full => Self::LogFull,
+            LogError::Malformed => Self::LogMalformed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+enum PiggyBankEvent {
+    Inserted {
+        from: Address,
+        amount: Amount,
+    },
+    Smashed {
+        owner: AccountAddress,
+        amount: Amount,
+    },
+    Withdrawn {
+        owner: AccountAddress,
+        amount: Amount,
+    },
 }
 
-#[init(contract = "PiggyBank")]
+#[derive(Debug, PartialEq, Eq, Serial, Reject)]
+enum InitError {
+    #[from(ParseError)]
+    ParseParams,
+    InvalidFeeBasisPoints,
+    InvalidBeneficiary,
+}
+
+#[init(
+    contract = "PiggyBank",
+    parameter = "InitParams",
+    event = "PiggyBankEvent"
+)]
 fn piggy_init<S: HasStateApi>(
-    _ctx: &impl HasInitContext,
-    _state_builder: &mut StateBuilder<S>,
-) -> InitResult<PiggyBankState> {
-    Ok(PiggyBankState::Intact)
+    ctx: &impl HasInitContext,
+    state_builder: &mut StateBuilder<S>,
+) -> Result<PiggyBankState<S>, InitError> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+    ensure!(
+        params.fee_basis_points <= 10000,
+        InitError::InvalidFeeBasisPoints
+    );
+    if let Some(beneficiary) = params.beneficiary {
+        ensure!(
+            matches!(beneficiary, Address::Account(_)),
+            InitError::InvalidBeneficiary
+        );
+    }
+
+    Ok(PiggyBankState::new(
+        ctx.init_origin(),
+        params.goal,
+        params.beneficiary,
+        params.fee_basis_points,
+        state_builder,
+    ))
 }
 
-#[receive(contract = "PiggyBank", name = "insert", payable)]
+#[receive(
+    contract = "PiggyBank",
+    name = "insert",
+    mutable,
+    payable,
+    enable_logger
+)]
 fn piggy_insert<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    host: &impl HasHost<PiggyBankState, StateApiType = S>,
-    _amount: Amount,
-) -> ReceiveResult<()> {
-    ensure!(*host.state() == PiggyBankState::Intact);
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<PiggyBankState<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> Result<(), InsertError> {
+    ensure!(
+        host.state().status == PiggyBankStatus::Intact,
+        InsertError::AlreadySmashed
+    );
+    ensure!(amount != Amount::zero(), InsertError::ZeroAmount);
+
+    let sender: Address = ctx.sender();
+    if let Address::Account(account) = sender {
+        let mut contribution = host
+            .state_mut()
+            .contributions
+            .entry(account)
+            .or_insert(Amount::zero());
+        *contribution += amount;
+    }
+
+    logger.log(&PiggyBankEvent::Inserted {
+        from: sender,
+        amount,
+    })?;
+
     Ok(())
 }
 
-#[receive(contract = "PiggyBank", name = "smash", mutable)]
+#[receive(
+    contract = "PiggyBank",
+    name = "smash",
+    mutable,
+    payable,
+    enable_logger
+)]
 fn piggy_smash<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<PiggyBankState, StateApiType = S>,
+    host: &mut impl HasHost<PiggyBankState<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
 ) -> Result<(), SmashError> {
-    let owner: AccountAddress = ctx.owner();
+    let owner: AccountAddress = host.state().owner;
     let sender: Address = ctx.sender();
     ensure!(sender.matches_account(&owner), SmashError::NotOwner);
     ensure!(
-        *host.state() == PiggyBankState::Intact,
+        host.state().status == PiggyBankStatus::Intact,
         SmashError::AlreadySmashed
     );
+    ensure!(amount == Amount::zero(), SmashError::AmountNotZero);
 
-    *host.state_mut() = PiggyBankState::Smashed;
+    host.state_mut().status = PiggyBankStatus::Smashed;
 
     let balance: Amount = host.self_balance();
-    let transfer_result: Result<(), TransferError> = host.invoke_transfer(&owner, balance);
+    let beneficiary: Option<Address> = host.state().beneficiary;
+    let fee_basis_points: u16 = host.state().fee_basis_points;
+
+    let fee: Amount = if fee_basis_points == 0 || beneficiary.is_none() {
+        Amount::zero()
+    } else {
+        let fee_micro_ccd: u64 = balance
+            .micro_ccd()
+            .checked_mul(fee_basis_points as u64)
+            .and_then(|product| product.checked_div(10000))
+            .ok_or(SmashError::Overflow)?;
+        Amount::from_micro_ccd(fee_micro_ccd)
+    };
+
+    if fee > Amount::zero() {
+        match beneficiary {
+            Some(Address::Account(beneficiary_account)) => {
+                let fee_transfer_result: Result<(), TransferError> =
+                    host.invoke_transfer(&beneficiary_account, fee);
+                ensure!(fee_transfer_result.is_ok(), SmashError::FeeTransferError);
+            }
+            Some(Address::Contract(_)) => return Err(SmashError::FeeTransferError),
+            None => (),
+        }
+    }
+
+    let remainder: Amount = Amount::from_micro_ccd(balance.micro_ccd() - fee.micro_ccd());
+    let transfer_result: Result<(), TransferError> = host.invoke_transfer(&owner, remainder);
     ensure!(transfer_result.is_ok(), SmashError::TransferError);
 
+    logger.log(&PiggyBankEvent::Smashed {
+        owner,
+        amount: remainder,
+    })?;
+
     Ok(())
 }
 
-#[receive(contract = "PiggyBank", name = "view")]
+#[receive(
+    contract = "PiggyBank",
+    name = "withdraw",
+    mutable,
+    parameter = "Amount",
+    enable_logger
+)]
+fn piggy_withdraw<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<PiggyBankState<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), WithdrawError> {
+    let owner: AccountAddress = host.state().owner;
+    let sender: Address = ctx.sender();
+    ensure!(sender.matches_account(&owner), WithdrawError::NotOwner);
+    ensure!(
+        host.state().status == PiggyBankStatus::Intact,
+        WithdrawError::AlreadySmashed
+    );
+
+    let amount: Amount = ctx.parameter_cursor().get()?;
+    ensure!(
+        amount <= host.self_balance(),
+        WithdrawError::InsufficientFunds
+    );
+
+    let transfer_result: Result<(), TransferError> = host.invoke_transfer(&owner, amount);
+    ensure!(transfer_result.is_ok(), WithdrawError::TransferError);
+
+    logger.log(&PiggyBankEvent::Withdrawn { owner, amount })?;
+
+    Ok(())
+}
+
+#[receive(contract = "PiggyBank", name = "goalReached", return_value = "bool")]
+fn piggy_goal_reached<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<PiggyBankState<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let state = host.state();
+    let reached = match state.goal {
+        Some(goal) => state.total_contributions() >= goal,
+        None => false,
+    };
+    Ok(reached)
+}
+
+#[receive(contract = "PiggyBank", name = "view", return_value = "PiggyBankView")]
 fn piggy_view<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    host: &impl HasHost<PiggyBankState, StateApiType = S>,
-) -> ReceiveResult<(PiggyBankState, Amount)> {
-    let current_state: PiggyBankState = *host.state();
-    let current_balance: Amount = host.self_balance();
-    Ok((current_state, current_balance))
+    host: &impl HasHost<PiggyBankState<S>, StateApiType = S>,
+) -> ReceiveResult<PiggyBankView> {
+    let state = host.state();
+    let contributions: Vec<(AccountAddress, Amount)> = state
+        .contributions
+        .iter()
+        .map(|(account, amount)| (*account, *amount))
+        .collect();
+
+    Ok(PiggyBankView {
+        owner: state.owner,
+        status: state.status,
+        balance: host.self_balance(),
+        goal: state.goal,
+        contributions,
+    })
 }
 
 #[concordium_cfg_test]
+#[allow(deprecated)]
 mod tests {
     use super::*;
     use test_infrastructure::*;
 
+    fn new_state<S: HasStateApi>(
+        owner: AccountAddress,
+        goal: Option<Amount>,
+        state_builder: &mut StateBuilder<S>,
+    ) -> PiggyBankState<S> {
+        PiggyBankState::new(owner, goal, None, 0, state_builder)
+    }
+
     #[concordium_test]
     fn test_init() {
-        let ctx: TestContext<TestInitOnlyData> = TestInitContext::empty();
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestInitOnlyData> = TestInitContext::empty();
+        ctx.set_init_origin(owner);
+        let parameter_bytes = to_bytes(&InitParams {
+            goal: None,
+            beneficiary: None,
+            fee_basis_points: 0,
+        });
+        ctx.set_parameter(&parameter_bytes);
         let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
 
-        let state_result: Result<PiggyBankState, Reject> = piggy_init(&ctx, &mut state_builder);
+        let state_result: Result<PiggyBankState<TestStateApi>, InitError> =
+            piggy_init(&ctx, &mut state_builder);
 
-        let state: PiggyBankState =
+        let state: PiggyBankState<TestStateApi> =
             state_result.expect_report("Contract initialization results in error.");
 
         claim_eq!(
-            state,
-            PiggyBankState::Intact,
+            state.status,
+            PiggyBankStatus::Intact,
             "Piggy bank state should be intact after initialization."
         );
+        claim_eq!(
+            state.owner,
+            owner,
+            "Piggy bank owner should be the init origin."
+        );
+        claim_eq!(
+            state.goal,
+            None,
+            "Piggy bank should have no goal by default."
+        );
+    }
+
+    #[concordium_test]
+    fn test_init_rejects_invalid_fee_basis_points() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestInitOnlyData> = TestInitContext::empty();
+        ctx.set_init_origin(owner);
+        let parameter_bytes = to_bytes(&InitParams {
+            goal: None,
+            beneficiary: None,
+            fee_basis_points: 10001,
+        });
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+
+        let state_result: Result<PiggyBankState<TestStateApi>, InitError> =
+            piggy_init(&ctx, &mut state_builder);
+
+        claim_eq!(
+            state_result.err(),
+            Some(InitError::InvalidFeeBasisPoints),
+            "Expected to fail with error InvalidFeeBasisPoints."
+        );
+    }
+
+    #[concordium_test]
+    fn test_init_rejects_contract_beneficiary() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestInitOnlyData> = TestInitContext::empty();
+        ctx.set_init_origin(owner);
+        let parameter_bytes = to_bytes(&InitParams {
+            goal: None,
+            beneficiary: Some(Address::Contract(ContractAddress::new(0, 0))),
+            fee_basis_points: 100,
+        });
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+
+        let state_result: Result<PiggyBankState<TestStateApi>, InitError> =
+            piggy_init(&ctx, &mut state_builder);
+
+        claim_eq!(
+            state_result.err(),
+            Some(InitError::InvalidBeneficiary),
+            "Expected to fail with error InvalidBeneficiary."
+        );
     }
 
     #[concordium_test]
     fn test_insert_intact() {
-        let ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
-        let host: TestHost<PiggyBankState> =
-            TestHost::new(PiggyBankState::Intact, TestStateBuilder::new());
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let depositor: AccountAddress = AccountAddress([1u8; 32]);
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(depositor));
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
         let amount: Amount = Amount::from_micro_ccd(100);
 
-        let result: Result<(), Reject> = piggy_insert(&ctx, &host, amount);
+        let result: Result<(), InsertError> = piggy_insert(&ctx, &mut host, amount, &mut logger);
 
         claim!(result.is_ok(), "Inserting CCD results in error");
+        claim_eq!(logger.logs.len(), 1, "Exactly one event should be logged.");
+        claim_eq!(
+            *host
+                .state()
+                .contributions
+                .get(&depositor)
+                .expect("Depositor should have a recorded contribution"),
+            amount,
+            "Depositor's contribution should be recorded."
+        );
+    }
+
+    #[concordium_test]
+    fn test_insert_zero_amount() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
+
+        let result: Result<(), InsertError> =
+            piggy_insert(&ctx, &mut host, Amount::zero(), &mut logger);
+
+        claim_eq!(
+            result,
+            Err(InsertError::ZeroAmount),
+            "Expected to fail with error ZeroAmount."
+        );
     }
 
     #[concordium_test]
     fn test_smash_intact() {
-        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
         let owner: AccountAddress = AccountAddress([0u8; 32]);
-        ctx.set_owner(owner);
-        let sender: Address = Address::Account(owner);
-        ctx.set_sender(sender);
-        let mut host: TestHost<PiggyBankState> =
-            TestHost::new(PiggyBankState::Intact, TestStateBuilder::new());
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(owner));
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
         let balance: Amount = Amount::from_micro_ccd(100);
         host.set_self_balance(balance);
 
-        let result: Result<(), SmashError> = piggy_smash(&ctx, &mut host);
+        let result: Result<(), SmashError> =
+            piggy_smash(&ctx, &mut host, Amount::zero(), &mut logger);
 
         claim!(
             result.is_ok(),
             "Smashing intact piggy bank results in error."
         );
         claim_eq!(
-            *host.state(),
-            PiggyBankState::Smashed,
+            host.state().status,
+            PiggyBankStatus::Smashed,
             "Piggy bank should be smashed."
         );
         claim_eq!(
@@ -125,21 +514,23 @@ mod tests {
             [(owner, balance)],
             "Smashing did not produce the correct transfers."
         );
+        claim_eq!(logger.logs.len(), 1, "Exactly one event should be logged.");
     }
 
     #[concordium_test]
     fn test_smash_intact_not_owner() {
-        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
         let owner: AccountAddress = AccountAddress([0u8; 32]);
-        ctx.set_owner(owner);
-        let sender: Address = Address::Account(AccountAddress([1u8; 32]));
-        ctx.set_sender(sender);
-        let mut host: TestHost<PiggyBankState> =
-            TestHost::new(PiggyBankState::Intact, TestStateBuilder::new());
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(AccountAddress([1u8; 32])));
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
         let balance: Amount = Amount::from_micro_ccd(100);
         host.set_self_balance(balance);
 
-        let result: Result<(), SmashError> = piggy_smash(&ctx, &mut host);
+        let result: Result<(), SmashError> =
+            piggy_smash(&ctx, &mut host, Amount::zero(), &mut logger);
 
         claim_eq!(
             result,
@@ -147,4 +538,169 @@ mod tests {
             "Expected to fail with error NotOwner."
         );
     }
+
+    #[concordium_test]
+    fn test_smash_intact_amount_not_zero() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(owner));
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
+        let balance: Amount = Amount::from_micro_ccd(100);
+        host.set_self_balance(balance);
+
+        let result: Result<(), SmashError> =
+            piggy_smash(&ctx, &mut host, Amount::from_micro_ccd(1), &mut logger);
+
+        claim_eq!(
+            result,
+            Err(SmashError::AmountNotZero),
+            "Expected to fail with error AmountNotZero."
+        );
+    }
+
+    #[concordium_test]
+    fn test_smash_splits_fee_to_beneficiary() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let beneficiary: AccountAddress = AccountAddress([2u8; 32]);
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(owner));
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = PiggyBankState::new(
+            owner,
+            None,
+            Some(Address::Account(beneficiary)),
+            250, // 2.5%
+            &mut state_builder,
+        );
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
+        let balance: Amount = Amount::from_micro_ccd(1000);
+        host.set_self_balance(balance);
+
+        let result: Result<(), SmashError> =
+            piggy_smash(&ctx, &mut host, Amount::zero(), &mut logger);
+
+        claim!(result.is_ok(), "Smashing with a fee results in error.");
+        let fee: Amount = Amount::from_micro_ccd(25);
+        let remainder: Amount = Amount::from_micro_ccd(975);
+        claim_eq!(
+            host.get_transfers(),
+            [(beneficiary, fee), (owner, remainder)],
+            "Smashing should transfer the fee to the beneficiary and the remainder to the owner."
+        );
+        claim_eq!(
+            fee.micro_ccd() + remainder.micro_ccd(),
+            balance.micro_ccd(),
+            "The fee and the remainder should sum to the original balance."
+        );
+    }
+
+    #[concordium_test]
+    fn test_goal_reached_multi_depositor() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let alice: AccountAddress = AccountAddress([1u8; 32]);
+        let bob: AccountAddress = AccountAddress([2u8; 32]);
+        let ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let goal: Amount = Amount::from_micro_ccd(150);
+        let state: PiggyBankState<TestStateApi> = new_state(owner, Some(goal), &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+
+        host.state_mut()
+            .contributions
+            .insert(alice, Amount::from_micro_ccd(100));
+
+        claim!(
+            !piggy_goal_reached(&ctx, &host).expect_report("goalReached should not error"),
+            "Goal should not be reached yet."
+        );
+
+        host.state_mut()
+            .contributions
+            .insert(bob, Amount::from_micro_ccd(50));
+
+        claim!(
+            piggy_goal_reached(&ctx, &host).expect_report("goalReached should not error"),
+            "Goal should be reached once contributions sum to the goal."
+        );
+    }
+
+    #[concordium_test]
+    fn test_withdraw_intact() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(owner));
+        let withdrawal: Amount = Amount::from_micro_ccd(40);
+        let parameter_bytes = to_bytes(&withdrawal);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
+        host.set_self_balance(Amount::from_micro_ccd(100));
+
+        let result: Result<(), WithdrawError> = piggy_withdraw(&ctx, &mut host, &mut logger);
+
+        claim!(result.is_ok(), "Withdrawing CCD results in error.");
+        claim_eq!(
+            host.get_transfers(),
+            [(owner, withdrawal)],
+            "Withdrawing did not produce the correct transfer."
+        );
+        claim_eq!(
+            host.state().status,
+            PiggyBankStatus::Intact,
+            "Piggy bank should remain intact after a partial withdrawal."
+        );
+        claim_eq!(logger.logs.len(), 1, "Exactly one event should be logged.");
+    }
+
+    #[concordium_test]
+    fn test_withdraw_insufficient_funds() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(owner));
+        let withdrawal: Amount = Amount::from_micro_ccd(150);
+        let parameter_bytes = to_bytes(&withdrawal);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
+        host.set_self_balance(Amount::from_micro_ccd(100));
+
+        let result: Result<(), WithdrawError> = piggy_withdraw(&ctx, &mut host, &mut logger);
+
+        claim_eq!(
+            result,
+            Err(WithdrawError::InsufficientFunds),
+            "Expected to fail with error InsufficientFunds."
+        );
+    }
+
+    #[concordium_test]
+    fn test_withdraw_not_owner() {
+        let owner: AccountAddress = AccountAddress([0u8; 32]);
+        let mut ctx: TestContext<TestReceiveOnlyData> = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(AccountAddress([1u8; 32])));
+        let withdrawal: Amount = Amount::from_micro_ccd(40);
+        let parameter_bytes = to_bytes(&withdrawal);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder: StateBuilder<TestStateApi> = TestStateBuilder::new();
+        let state: PiggyBankState<TestStateApi> = new_state(owner, None, &mut state_builder);
+        let mut host: TestHost<PiggyBankState<TestStateApi>> = TestHost::new(state, state_builder);
+        let mut logger: TestLogger = TestLogger::init();
+        host.set_self_balance(Amount::from_micro_ccd(100));
+
+        let result: Result<(), WithdrawError> = piggy_withdraw(&ctx, &mut host, &mut logger);
+
+        claim_eq!(
+            result,
+            Err(WithdrawError::NotOwner),
+            "Expected to fail with error NotOwner."
+        );
+    }
 }